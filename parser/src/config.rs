@@ -0,0 +1,165 @@
+//! Configuration accepted by the parser binary, supplied as a `--config-file`.
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Configuration for a single parsing invocation. This is deserialized from the
+/// `--config-file` given to the `parse` subcommand, and its schema is reported by the
+/// `spec` subcommand so callers can discover what's honored before they send data.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ParseConfig {
+    /// A JSON schema used to inform how values are projected and coerced. Defaults to
+    /// permissive (`true`) when absent, in which case types are instead inferred by
+    /// sampling input records (see `format::inference`).
+    #[serde(default = "default_schema")]
+    pub schema: Value,
+
+    /// Explicit mappings of field name to the JSON pointer it should be projected into.
+    /// These always take precedence over projections derived from the schema or from
+    /// sampled records.
+    #[serde(default)]
+    pub projections: BTreeMap<String, String>,
+
+    /// The maximum normalized Levenshtein distance (0.0-1.0, as a fraction of the longer
+    /// collated name's length) at which a parsed column name that has no exact match is
+    /// still fuzzy-matched to a projection. Defaults to `0.15`, tolerating small typos or
+    /// stray whitespace without risking false matches between unrelated columns.
+    #[serde(default = "default_fuzzy_match_threshold")]
+    pub fuzzy_match_threshold: f64,
+
+    /// The input format to parse, one of the values reported by the `spec` subcommand's
+    /// `supported_formats` (`csv`, `tsv`, `json`, `jsonl`). Defaults to `jsonl`.
+    #[serde(default = "default_format")]
+    pub format: String,
+}
+
+fn default_fuzzy_match_threshold() -> f64 {
+    0.15
+}
+
+fn default_format() -> String {
+    "jsonl".to_string()
+}
+
+fn default_schema() -> Value {
+    Value::Bool(true)
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        ParseConfig {
+            schema: default_schema(),
+            projections: BTreeMap::new(),
+            fuzzy_match_threshold: default_fuzzy_match_threshold(),
+            format: default_format(),
+        }
+    }
+}
+
+/// The serialization formats a config file may be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+/// Detects the format of a config file from its extension, defaulting to JSON for
+/// extensionless paths (e.g. stdin placeholders) so existing JSON-only callers keep working.
+fn detect_format(path: &str) -> ConfigFormat {
+    match std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+        Some("toml") => ConfigFormat::Toml,
+        _ => ConfigFormat::Json,
+    }
+}
+
+/// Parses `contents` as a `T`, trying each supported format in turn. Used when the format
+/// can't be determined from a file extension, e.g. a config piped in over stdin.
+fn parse_any_format<T: DeserializeOwned>(contents: &str) -> Result<T, anyhow::Error> {
+    if let Ok(value) = serde_json::from_str(contents) {
+        return Ok(value);
+    }
+    if let Ok(value) = serde_yaml::from_str(contents) {
+        return Ok(value);
+    }
+    Ok(toml::from_str(contents)?)
+}
+
+fn parse_format<T: DeserializeOwned>(contents: &str, format: ConfigFormat) -> Result<T, anyhow::Error> {
+    Ok(match format {
+        ConfigFormat::Json => serde_json::from_str(contents)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(contents)?,
+        ConfigFormat::Toml => toml::from_str(contents)?,
+    })
+}
+
+/// Loads a `T` from a file at `path`, detecting JSON/YAML/TOML from its extension
+/// (`.json`, `.yaml`/`.yml`, `.toml`), or by trial-parsing each format in turn if the
+/// extension doesn't indicate one.
+fn load_file<T: DeserializeOwned>(path: &str) -> Result<T, anyhow::Error> {
+    let contents = std::fs::read_to_string(path)?;
+    match std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("json") | Some("yaml") | Some("yml") | Some("toml") => {
+            parse_format(&contents, detect_format(path))
+        }
+        _ => parse_any_format(&contents),
+    }
+}
+
+/// Loads a `ParseConfig` from a file at `path`.
+pub fn load_config_file(path: &str) -> Result<ParseConfig, anyhow::Error> {
+    load_file(path)
+}
+
+/// An overlay config file, layered on top of a base `ParseConfig` by `load_with_overlay`.
+/// Every field is optional (unlike `ParseConfig`, which fills each in with a default) so
+/// that an overlay can tell "the author explicitly set this" apart from "the author didn't
+/// mention this" — e.g. explicitly resetting a restrictive base schema to `true` must not
+/// be indistinguishable from omitting `schema` altogether.
+#[derive(Debug, Clone, Deserialize)]
+struct OverlayConfig {
+    schema: Option<Value>,
+    #[serde(default)]
+    projections: BTreeMap<String, String>,
+    fuzzy_match_threshold: Option<f64>,
+    format: Option<String>,
+}
+
+/// Loads a base config and, if given, layers an overlay config on top of it: the overlay's
+/// `projections` entries take precedence — exactly as config projections already override
+/// schema/sample-inferred ones in `build_projections` — and any other overlay field that was
+/// actually present in the overlay file replaces the base's value wholesale.
+pub fn load_with_overlay(
+    base_path: &str,
+    overlay_path: Option<&str>,
+) -> Result<ParseConfig, anyhow::Error> {
+    let mut config = load_config_file(base_path)?;
+    let Some(overlay_path) = overlay_path else {
+        return Ok(config);
+    };
+    let overlay: OverlayConfig = load_file(overlay_path)?;
+
+    if let Some(schema) = overlay.schema {
+        config.schema = schema;
+    }
+    if let Some(threshold) = overlay.fuzzy_match_threshold {
+        config.fuzzy_match_threshold = threshold;
+    }
+    if let Some(format) = overlay.format {
+        config.format = format;
+    }
+    for (field, pointer) in overlay.projections {
+        config.projections.insert(field, pointer);
+    }
+    Ok(config)
+}
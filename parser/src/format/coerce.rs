@@ -0,0 +1,175 @@
+//! Coerces raw string cells from tabular formats (CSV/TSV) into typed JSON values, using
+//! the `TypeInfo` that `projection::build_projections` resolves for each column. This is
+//! what actually puts the inferred/configured type information to use: without it, every
+//! cell from a tabular parser would stay a JSON string.
+
+use super::projection::TypeInfo;
+use json::schema::types;
+use serde_json::Value;
+
+/// The vocabulary of strings accepted as `true`/`false` when coercing a cell to a boolean,
+/// compared case-insensitively.
+#[derive(Debug, Clone)]
+pub struct BooleanVocabulary {
+    pub true_values: Vec<String>,
+    pub false_values: Vec<String>,
+}
+
+impl Default for BooleanVocabulary {
+    fn default() -> Self {
+        BooleanVocabulary {
+            true_values: vec!["true".to_string(), "1".to_string()],
+            false_values: vec!["false".to_string(), "0".to_string()],
+        }
+    }
+}
+
+/// Coerces a single raw cell value according to `info.possible_types`, returning `None`
+/// when the cell should be omitted from the document entirely (an empty cell at a location
+/// that isn't required and doesn't admit null).
+///
+/// - If `possible_types` is `None`, no type information is available, so the raw string is
+///   returned unchanged.
+/// - If the set is ambiguous (contains more than one of string/integer/number/boolean), the
+///   raw string is returned unchanged, since we can't tell which type was intended.
+/// - An empty cell becomes `Value::Null` when the set admits null. Otherwise it's omitted
+///   (`None`) when the location isn't required, or becomes `Value::Null` anyway when it is,
+///   so that required locations are never silently dropped from the document.
+pub fn coerce(raw: &str, info: &TypeInfo, vocab: &BooleanVocabulary) -> Option<Value> {
+    let possible_types = match info.possible_types {
+        Some(t) => t,
+        None => return Some(Value::String(raw.to_string())),
+    };
+
+    if raw.is_empty() {
+        return if possible_types.overlaps(types::NULL) {
+            Some(Value::Null)
+        } else if !info.must_exist {
+            None
+        } else {
+            Some(Value::Null)
+        };
+    }
+
+    let without_null = possible_types - types::NULL;
+    if without_null == types::INTEGER {
+        return Some(
+            raw.parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| raw.parse::<f64>().map(Value::from))
+                .unwrap_or_else(|_| Value::String(raw.to_string())),
+        );
+    }
+    if without_null == types::NUMBER {
+        return Some(
+            raw.parse::<f64>()
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::String(raw.to_string())),
+        );
+    }
+    if without_null == types::BOOLEAN {
+        return Some(coerce_boolean(raw, vocab));
+    }
+
+    Some(Value::String(raw.to_string()))
+}
+
+fn coerce_boolean(raw: &str, vocab: &BooleanVocabulary) -> Value {
+    if vocab.true_values.iter().any(|v| v.eq_ignore_ascii_case(raw)) {
+        Value::Bool(true)
+    } else if vocab
+        .false_values
+        .iter()
+        .any(|v| v.eq_ignore_ascii_case(raw))
+    {
+        Value::Bool(false)
+    } else {
+        Value::String(raw.to_string())
+    }
+}
+
+/// Coerces `raw` and, if a value results, writes it into `document` at `info.target_location`.
+pub fn coerce_into(
+    document: &mut Value,
+    raw: &str,
+    info: &TypeInfo,
+    vocab: &BooleanVocabulary,
+) {
+    if let Some(value) = coerce(raw, info, vocab) {
+        if let Some(slot) = info.target_location.create_value(document) {
+            *slot = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use doc::Pointer;
+
+    fn info(possible_types: Option<types::Set>, must_exist: bool) -> TypeInfo {
+        TypeInfo {
+            possible_types,
+            must_exist,
+            target_location: Pointer::from_str("/field"),
+        }
+    }
+
+    #[test]
+    fn integer_is_parsed() {
+        let i = info(Some(types::INTEGER), true);
+        assert_eq!(coerce("42", &i, &BooleanVocabulary::default()), Some(Value::from(42i64)));
+    }
+
+    #[test]
+    fn oversized_integer_falls_back_to_float() {
+        let i = info(Some(types::INTEGER), true);
+        let result = coerce("99999999999999999999", &i, &BooleanVocabulary::default());
+        assert_eq!(result, Some(Value::from(1e20)));
+    }
+
+    #[test]
+    fn ambiguous_type_set_stays_a_string() {
+        let i = info(Some(types::INTEGER | types::STRING), true);
+        assert_eq!(
+            coerce("42", &i, &BooleanVocabulary::default()),
+            Some(Value::String("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn no_type_info_stays_a_string() {
+        let i = info(None, true);
+        assert_eq!(
+            coerce("42", &i, &BooleanVocabulary::default()),
+            Some(Value::String("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn empty_cell_becomes_null_when_admitted() {
+        let i = info(Some(types::INTEGER | types::NULL), true);
+        assert_eq!(coerce("", &i, &BooleanVocabulary::default()), Some(Value::Null));
+    }
+
+    #[test]
+    fn empty_cell_is_omitted_when_not_required_and_not_nullable() {
+        let i = info(Some(types::INTEGER), false);
+        assert_eq!(coerce("", &i, &BooleanVocabulary::default()), None);
+    }
+
+    #[test]
+    fn boolean_vocabulary_is_respected() {
+        let i = info(Some(types::BOOLEAN), true);
+        assert_eq!(
+            coerce("yes", &i, &BooleanVocabulary::default()),
+            Some(Value::String("yes".to_string()))
+        );
+
+        let vocab = BooleanVocabulary {
+            true_values: vec!["yes".to_string()],
+            false_values: vec!["no".to_string()],
+        };
+        assert_eq!(coerce("yes", &i, &vocab), Some(Value::Bool(true)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Infers `TypeInfo` for parsed records by sampling, for use when the configured schema
+//! (or a projected location within it) provides no type guidance. This mirrors Flow's
+//! `enable-schema-inference` behavior, but runs at parse time over the input itself
+//! rather than over a catalog build.
+
+use super::projection::{derive_field_names, TypeInfo};
+use doc::Pointer;
+use json::schema::types;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// The default number of leading records sampled to infer types, bounding both the time
+/// spent sampling and the memory used to track observed locations.
+pub const DEFAULT_SAMPLE_LIMIT: usize = 1000;
+
+#[derive(Default)]
+struct Observed {
+    types: types::Set,
+    seen_in: usize,
+}
+
+/// Infers a `TypeInfo` for every JSON pointer observed across `samples`, which should be
+/// at most `DEFAULT_SAMPLE_LIMIT` (or a caller-bounded number of) leading records read from
+/// the input. For each location, `possible_types` is the union of JSON types seen there,
+/// and `must_exist` is true only if the location was present in every sampled record.
+/// A location that's missing from some samples has its type set widened to include null,
+/// since parsers must treat "absent" the same as "null" when coercing a missing cell.
+pub fn infer_from_samples(samples: &[Value]) -> BTreeMap<String, TypeInfo> {
+    let mut observed: BTreeMap<String, Observed> = BTreeMap::new();
+
+    for sample in samples {
+        walk(sample, &mut String::new(), &mut observed);
+    }
+
+    let total = samples.len();
+    let mut results = BTreeMap::new();
+    for (pointer, mut info) in observed {
+        if info.seen_in < total {
+            info.types = info.types | types::NULL;
+        }
+        let target_location = Pointer::from_str(&pointer);
+        let type_info = TypeInfo {
+            must_exist: info.seen_in == total,
+            possible_types: Some(info.types),
+            target_location: target_location.clone(),
+        };
+        for resolved_field in derive_field_names(&pointer) {
+            results.insert(resolved_field, type_info.clone());
+        }
+    }
+    results
+}
+
+fn walk(value: &Value, pointer: &mut String, out: &mut BTreeMap<String, Observed>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, child) in fields {
+                let reset_to = pointer.len();
+                pointer.push('/');
+                pointer.push_str(key);
+                observe(pointer, child, out);
+                walk(child, pointer, out);
+                pointer.truncate(reset_to);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                let reset_to = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+                observe(pointer, child, out);
+                walk(child, pointer, out);
+                pointer.truncate(reset_to);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn observe(pointer: &str, value: &Value, out: &mut BTreeMap<String, Observed>) {
+    let entry = out
+        .entry(pointer.to_string())
+        .or_insert_with(Observed::default);
+    entry.types = entry.types | types::Set::for_value(value);
+    entry.seen_in += 1;
+}
+
+/// Merges schema-derived projections with those inferred from sampled records. A
+/// schema-derived entry only wins when it actually carries type information
+/// (`possible_types: Some(..)`); entries with `possible_types: None` are placeholders
+/// inserted for a config `projections` pointer that couldn't be located within the schema
+/// (see `projection::build_projections`). For those, a sampled guess for the same field name
+/// supplies `possible_types` and `must_exist`, while the configured `target_location` is kept.
+pub fn merge_inferred(
+    schema_derived: BTreeMap<String, TypeInfo>,
+    sampled: BTreeMap<String, TypeInfo>,
+) -> BTreeMap<String, TypeInfo> {
+    let mut merged = sampled;
+    for (field, info) in schema_derived {
+        if info.possible_types.is_some() {
+            merged.insert(field, info);
+        } else if let Some(sampled_info) = merged.get(&field).cloned() {
+            let combined = TypeInfo {
+                target_location: info.target_location,
+                ..sampled_info
+            };
+            merged.insert(field, combined);
+        } else {
+            merged.insert(field, info);
+        }
+    }
+    merged
+}
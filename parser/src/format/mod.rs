@@ -0,0 +1,7 @@
+//! Format-specific parsing support, plus the shared projection and type-inference
+//! machinery used to map parsed tabular/semi-structured data into typed JSON documents.
+
+pub mod coerce;
+pub mod inference;
+pub mod projection;
+pub mod tabular;
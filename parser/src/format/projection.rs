@@ -1,5 +1,6 @@
 //! Types for reasoning about projections of tabular data into potentially nested JSON.
 use crate::config::ParseConfig;
+use crate::format::inference;
 use caseless::Caseless;
 use doc::inference::{Exists, Shape};
 use doc::{Pointer, Schema, SchemaIndex};
@@ -56,9 +57,17 @@ where
 /// aliases for each inferred location, but projections from the config will always take
 /// precedence.
 ///
+/// `samples` is a bounded prefix of records already parsed from the input (see
+/// `format::inference`), used to fill in type information for locations the schema doesn't
+/// cover, e.g. because the schema is absent (`true`/null) or doesn't mention that location.
+/// Schema-derived entries always win when a location is covered by both.
+///
 /// Parsers can use the returned map to lookup type information based on parsed column names.
-#[tracing::instrument(skip(config))]
-pub fn build_projections(config: &ParseConfig) -> Result<BTreeMap<String, TypeInfo>, BuildError> {
+#[tracing::instrument(skip(config, samples))]
+pub fn build_projections(
+    config: &ParseConfig,
+    samples: &[Value],
+) -> Result<BTreeMap<String, TypeInfo>, BuildError> {
     let schema_uri = url::Url::parse("whatever://placeholder").unwrap();
     let schema_json = if config.schema.is_null() {
         &Value::Bool(true)
@@ -105,13 +114,130 @@ pub fn build_projections(config: &ParseConfig) -> Result<BTreeMap<String, TypeIn
         results.insert(collate(field.chars()).collect(), projection);
     }
 
+    if !samples.is_empty() {
+        let sampled = inference::infer_from_samples(samples);
+        results = inference::merge_inferred(results, sampled);
+    }
+
     Ok(results)
 }
 
+/// Maps a `types::Set` to the value a JSON schema's `type` keyword would take for it: a single
+/// type name if only one type is possible, or an array of names if more than one (mirroring how
+/// JSON Schema itself represents type unions).
+pub fn schema_type_for(possible_types: types::Set) -> Value {
+    let mut names = Vec::new();
+    if possible_types.overlaps(types::NULL) {
+        names.push("null");
+    }
+    if possible_types.overlaps(types::BOOLEAN) {
+        names.push("boolean");
+    }
+    if possible_types.overlaps(types::INTEGER) {
+        names.push("integer");
+    }
+    if possible_types.overlaps(types::NUMBER) {
+        names.push("number");
+    }
+    if possible_types.overlaps(types::STRING) {
+        names.push("string");
+    }
+    if possible_types.overlaps(types::OBJECT) {
+        names.push("object");
+    }
+    if possible_types.overlaps(types::ARRAY) {
+        names.push("array");
+    }
+
+    if names.len() == 1 {
+        Value::String(names[0].to_string())
+    } else {
+        Value::Array(names.into_iter().map(|n| Value::String(n.to_string())).collect())
+    }
+}
+
+/// Resolves a parsed column name against `columns`, the map built by `build_projections`.
+/// Tries an exact (collated) match first; if none is found, falls back to the single
+/// candidate key whose normalized Levenshtein distance to the column name is both at or below
+/// `threshold` and strictly the smallest among all keys. If two or more keys tie for the
+/// smallest distance, declines to guess and emits a `tracing::warn!` instead, since picking
+/// either one risks silently routing data to the wrong location.
+pub fn resolve_column<'a>(
+    columns: &'a BTreeMap<String, TypeInfo>,
+    raw_column: &str,
+    threshold: f64,
+) -> Option<&'a TypeInfo> {
+    let collated: String = collate(raw_column.chars()).collect();
+    if let Some(info) = columns.get(&collated) {
+        return Some(info);
+    }
+
+    let needle: Vec<char> = collated.chars().collect();
+    let mut best: Option<(&str, usize, f64)> = None;
+    let mut tied = false;
+
+    for key in columns.keys() {
+        let hay: Vec<char> = key.chars().collect();
+        let longest = needle.len().max(hay.len());
+        if longest == 0 {
+            continue;
+        }
+        let distance = levenshtein(&needle, &hay);
+        let normalized = distance as f64 / longest as f64;
+        if normalized > threshold {
+            continue;
+        }
+        match best {
+            Some((_, _, best_normalized)) if normalized < best_normalized => {
+                best = Some((key, distance, normalized));
+                tied = false;
+            }
+            Some((_, _, best_normalized)) if normalized == best_normalized => {
+                tied = true;
+            }
+            None => best = Some((key, distance, normalized)),
+            _ => {}
+        }
+    }
+
+    match best {
+        Some((key, ..)) if !tied => columns.get(key),
+        Some(_) => {
+            tracing::warn!(
+                column = raw_column,
+                "column name matches multiple projections within the fuzzy-match threshold; declining to guess"
+            );
+            None
+        }
+        None => None,
+    }
+}
+
+/// Computes the Levenshtein (edit) distance between two already-collated `char` sequences.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, &a_ch) in a.iter().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+        for (j, &b_ch) in b.iter().enumerate() {
+            let temp = row[j + 1];
+            row[j + 1] = if a_ch == b_ch {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j + 1])
+            };
+            prev_diag = temp;
+        }
+    }
+
+    row[b.len()]
+}
+
 /// Returns a possibly 0-length collection of field names derived from the given JSON pointer.
 /// The field names will represent a variety of possible mappings from fields to the location,
 /// which will be used to make a best-effort lookup of columns from a tabular data file.
-fn derive_field_names(pointer: &str) -> Vec<String> {
+pub(crate) fn derive_field_names(pointer: &str) -> Vec<String> {
     use doc::ptr::Token;
 
     if pointer.is_empty() {
@@ -250,7 +376,101 @@ mod test {
             }),
             ..Default::default()
         };
-        let result = build_projections(&config).expect("failed to build projections");
+        let result = build_projections(&config, &[]).expect("failed to build projections");
         insta::assert_debug_snapshot!(result);
     }
+
+    #[test]
+    fn schemaless_locations_are_inferred_from_samples() {
+        let config = ParseConfig {
+            schema: json!(true),
+            ..Default::default()
+        };
+        let samples = vec![
+            json!({"id": 1, "name": "a", "tag": "x"}),
+            json!({"id": 2, "name": "b"}),
+        ];
+        let result = build_projections(&config, &samples).expect("failed to build projections");
+
+        let id = result.get("id").expect("id should be inferred");
+        assert!(id.must_exist);
+        assert_eq!(id.possible_types, Some(types::INTEGER));
+
+        let tag = result.get("tag").expect("tag should be inferred");
+        assert!(!tag.must_exist);
+        assert_eq!(tag.possible_types, Some(types::STRING | types::NULL));
+    }
+
+    #[test]
+    fn sampled_types_survive_an_unlocatable_config_projection() {
+        // "user_id" points nowhere in the schema, so build_projections inserts a
+        // `possible_types: None` placeholder for it. Sampled records for the same field
+        // name should still provide real type information rather than being discarded.
+        let config = ParseConfig {
+            projections: map_of!("user_id" => "/user/id"),
+            schema: json!({"type": "object"}),
+            ..Default::default()
+        };
+        let samples = vec![json!({"user_id": 1}), json!({"user_id": 2})];
+        let result = build_projections(&config, &samples).expect("failed to build projections");
+
+        let info = result.get("user_id").expect("user_id should be present");
+        assert_eq!(info.possible_types, Some(types::INTEGER));
+        assert!(info.must_exist);
+    }
+
+    #[test]
+    fn fuzzy_match_resolves_a_typo_d_column() {
+        let config = ParseConfig {
+            projections: map_of!("user_id" => "/user/id"),
+            ..Default::default()
+        };
+        let columns = build_projections(&config, &[]).expect("failed to build projections");
+
+        // A single dropped character is within the default threshold.
+        let info = resolve_column(&columns, "usr_id", config.fuzzy_match_threshold)
+            .expect("should fuzzy-match despite the typo");
+        assert_eq!(info.target_location, Pointer::from_str("/user/id"));
+
+        // Too different from any known column to match.
+        assert!(resolve_column(&columns, "completely_different", config.fuzzy_match_threshold)
+            .is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_declines_to_guess_on_a_tie() {
+        let config = ParseConfig {
+            projections: map_of!("user_id" => "/user/id", "uber_id" => "/uber/id"),
+            ..Default::default()
+        };
+        let columns = build_projections(&config, &[]).expect("failed to build projections");
+
+        // Equidistant from both "user_id" and "uber_id".
+        assert!(resolve_column(&columns, "uter_id", config.fuzzy_match_threshold).is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_compares_normalized_not_raw_distance() {
+        // "data" and "danax" are both at raw edit distance 1 from "dana", but "danax" is
+        // longer, so its *normalized* distance (0.2) is smaller than "data"'s (0.25).
+        // Comparing raw distances would falsely treat them as tied and decline to match.
+        let config = ParseConfig {
+            projections: map_of!("data" => "/a", "danax" => "/b"),
+            fuzzy_match_threshold: 0.3,
+            ..Default::default()
+        };
+        let columns = build_projections(&config, &[]).expect("failed to build projections");
+
+        let info = resolve_column(&columns, "dana", config.fuzzy_match_threshold)
+            .expect("should match \"danax\" outright, not tie");
+        assert_eq!(info.target_location, Pointer::from_str("/b"));
+    }
+
+    #[test]
+    fn levenshtein_distance_is_correct() {
+        let a: Vec<char> = "kitten".chars().collect();
+        let b: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein(&a, &b), 3);
+        assert_eq!(levenshtein(&a, &a), 0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! Reads CSV/TSV input into JSON documents, one per row, using `projection::build_projections`
+//! to resolve each column header to the location it should be placed at within the document.
+
+use crate::config::ParseConfig;
+use crate::format::coerce::{self, BooleanVocabulary};
+use crate::format::inference::DEFAULT_SAMPLE_LIMIT;
+use crate::format::projection::{self, TypeInfo};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// Parses delimited input (CSV with `b','`, TSV with `b'\t'`) into a vector of documents, one
+/// per row. Column headers are resolved against projections built from `config`'s schema and
+/// explicit `projections`, augmented by sampling the input's own rows when those don't cover
+/// a column (see `format::inference`).
+///
+/// Only the first `DEFAULT_SAMPLE_LIMIT` rows are buffered as raw `(String, String)` rows, to
+/// build the sample that schema inference runs against; every row after that is converted
+/// straight into its document as it's read. All documents are collected into the returned
+/// vector, so the whole parsed input is held in memory.
+pub fn parse_tabular(
+    config: &ParseConfig,
+    input: impl Read,
+    delimiter: u8,
+) -> Result<Vec<Value>, anyhow::Error> {
+    Ok(parse_tabular_inner(config, input, delimiter)?.0)
+}
+
+/// Like `parse_tabular`, but also returns the JSON schema inferred for each column header, so
+/// a caller passing `--emit-inferred-schema` can persist what was inferred about this input
+/// instead of re-deriving it downstream.
+pub fn parse_tabular_with_schema(
+    config: &ParseConfig,
+    input: impl Read,
+    delimiter: u8,
+) -> Result<(Vec<Value>, Value), anyhow::Error> {
+    let (documents, headers, projections) = parse_tabular_inner(config, input, delimiter)?;
+    let schema = inferred_schema(&headers, &projections, config.fuzzy_match_threshold);
+    Ok((documents, schema))
+}
+
+fn parse_tabular_inner(
+    config: &ParseConfig,
+    input: impl Read,
+    delimiter: u8,
+) -> Result<(Vec<Value>, Vec<String>, BTreeMap<String, TypeInfo>), anyhow::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(input);
+
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_string()).collect();
+    let to_row = |headers: &[String], record: &csv::StringRecord| -> Vec<(String, String)> {
+        headers
+            .iter()
+            .cloned()
+            .zip(record.iter().map(|cell| cell.to_string()))
+            .collect()
+    };
+
+    let mut records = reader.records();
+    let mut buffered_rows = Vec::new();
+    for record in records.by_ref().take(DEFAULT_SAMPLE_LIMIT) {
+        buffered_rows.push(to_row(&headers, &record?));
+    }
+
+    let samples: Vec<Value> = buffered_rows
+        .iter()
+        .map(|row| {
+            Value::Object(
+                row.iter()
+                    .map(|(header, raw)| {
+                        let value = if raw.is_empty() {
+                            Value::Null
+                        } else if let Ok(i) = raw.parse::<i64>() {
+                            Value::from(i)
+                        } else if let Some(f) = raw.parse::<f64>().ok().filter(|f| f.is_finite()) {
+                            Value::from(f)
+                        } else if let Ok(b) = raw.parse::<bool>() {
+                            Value::Bool(b)
+                        } else {
+                            Value::String(raw.clone())
+                        };
+                        (header.clone(), value)
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let projections = projection::build_projections(config, &samples)?;
+    let vocab = BooleanVocabulary::default();
+
+    let mut documents: Vec<Value> = buffered_rows
+        .into_iter()
+        .map(|row| build_document(row, &projections, config.fuzzy_match_threshold, &vocab))
+        .collect();
+
+    for record in records {
+        let row = to_row(&headers, &record?);
+        documents.push(build_document(
+            row,
+            &projections,
+            config.fuzzy_match_threshold,
+            &vocab,
+        ));
+    }
+
+    Ok((documents, headers, projections))
+}
+
+/// Builds a JSON schema describing the type inferred for each column header, looking each one
+/// up the same way a parsed row would (see `projection::resolve_column`) so the emitted schema
+/// reflects exactly what this run actually used to coerce cells, fuzzy-matched columns included.
+fn inferred_schema(
+    headers: &[String],
+    projections: &BTreeMap<String, TypeInfo>,
+    threshold: f64,
+) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for header in headers {
+        let Some(info) = projection::resolve_column(projections, header, threshold) else {
+            continue;
+        };
+        let schema = match info.possible_types {
+            Some(possible_types) => json!({ "type": projection::schema_type_for(possible_types) }),
+            None => Value::Bool(true),
+        };
+        properties.insert(header.clone(), schema);
+        if info.must_exist {
+            required.push(Value::String(header.clone()));
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn build_document(
+    row: Vec<(String, String)>,
+    projections: &BTreeMap<String, TypeInfo>,
+    threshold: f64,
+    vocab: &BooleanVocabulary,
+) -> Value {
+    let mut doc = Value::Object(Map::new());
+    for (header, raw) in row {
+        match projection::resolve_column(projections, &header, threshold) {
+            Some(info) => coerce::coerce_into(&mut doc, &raw, info, vocab),
+            None => {
+                if let Value::Object(map) = &mut doc {
+                    map.insert(header, Value::String(raw));
+                }
+            }
+        }
+    }
+    doc
+}
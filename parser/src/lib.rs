@@ -0,0 +1,11 @@
+//! Library crate for the tabular/JSON parser: configuration, format-specific
+//! parsing, and the projection and type-inference machinery shared by them.
+
+pub mod config;
+pub mod format;
+pub mod spec;
+
+pub use config::ParseConfig;
+
+/// A source of raw bytes to be parsed, e.g. a file or stdin.
+pub type Input = Box<dyn std::io::Read + Send>;
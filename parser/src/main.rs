@@ -0,0 +1,108 @@
+use clap::{Parser, Subcommand};
+use parser::config::ParseConfig;
+use parser::format::tabular;
+use std::io::{BufRead, Write};
+
+#[derive(Parser)]
+#[command(name = "parser")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parses records from stdin according to `--config-file`, emitting parsed JSON
+    /// documents as newline-delimited JSON on stdout.
+    Parse {
+        #[arg(long)]
+        config_file: String,
+        /// An optional second config file layered on top of `--config-file`, with its
+        /// `projections` taking precedence. Lets shared projection sets be authored once
+        /// and reused across captures via a thin overlay.
+        #[arg(long)]
+        overlay_config_file: Option<String>,
+        /// For CSV/TSV input, emit the schema inferred for each column as a leading stdout
+        /// record, `{"inferred_schema": <schema>}`, before any parsed documents. Lets a
+        /// downstream stage persist what was inferred instead of re-deriving it later. Has
+        /// no effect for JSON/JSONL input, which isn't passed through type inference.
+        #[arg(long)]
+        emit_inferred_schema: bool,
+    },
+    /// Reports this binary's capabilities as a single JSON document on stdout, so a host
+    /// process can negotiate behavior before it starts streaming data.
+    Spec,
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_env("PARSER_LOG"))
+        .init();
+
+    match Cli::parse().command {
+        Command::Parse {
+            config_file,
+            overlay_config_file,
+            emit_inferred_schema,
+        } => run_parse(&config_file, overlay_config_file.as_deref(), emit_inferred_schema),
+        Command::Spec => run_spec(),
+    }
+}
+
+fn run_spec() -> anyhow::Result<()> {
+    let stdout = std::io::stdout();
+    serde_json::to_writer(stdout.lock(), &parser::spec::build_spec())?;
+    Ok(())
+}
+
+fn run_parse(
+    config_file: &str,
+    overlay_config_file: Option<&str>,
+    emit_inferred_schema: bool,
+) -> anyhow::Result<()> {
+    let config: ParseConfig = parser::config::load_with_overlay(config_file, overlay_config_file)?;
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    let documents = match config.format.as_str() {
+        "csv" | "tsv" => {
+            let delimiter = if config.format == "csv" { b',' } else { b'\t' };
+            if emit_inferred_schema {
+                let (documents, schema) =
+                    tabular::parse_tabular_with_schema(&config, stdin.lock(), delimiter)?;
+                writeln!(
+                    out,
+                    "{}",
+                    serde_json::to_string(&serde_json::json!({ "inferred_schema": schema }))?
+                )?;
+                documents
+            } else {
+                tabular::parse_tabular(&config, stdin.lock(), delimiter)?
+            }
+        }
+        "json" => serde_json::Deserializer::from_reader(stdin.lock())
+            .into_iter::<serde_json::Value>()
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => parse_jsonl(stdin.lock())?,
+    };
+
+    for doc in documents {
+        writeln!(out, "{}", serde_json::to_string(&doc)?)?;
+    }
+
+    Ok(())
+}
+
+fn parse_jsonl(input: impl BufRead) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut documents = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        documents.push(serde_json::from_str(&line)?);
+    }
+    Ok(documents)
+}
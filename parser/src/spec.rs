@@ -0,0 +1,34 @@
+//! The `spec` subcommand: reports what this parser binary supports, so a host process can
+//! negotiate behavior (formats, config options) before it starts streaming data.
+
+use crate::config::ParseConfig;
+use schemars::schema_for;
+use serde::Serialize;
+
+/// The protocol version implemented by this binary, as a `(major, minor)` tuple. Bump the
+/// major version on breaking changes to the `spec`/`parse` CLI contract, and the minor
+/// version when adding backwards-compatible capabilities.
+pub const PROTOCOL_VERSION: (u32, u32) = (1, 0);
+
+/// Input formats this binary knows how to parse.
+pub const SUPPORTED_FORMATS: &[&str] = &["csv", "tsv", "json", "jsonl"];
+
+#[derive(Debug, Serialize)]
+pub struct Spec {
+    /// The semver version of this binary, taken from the crate's own version at build time.
+    pub version: String,
+    pub protocol_version: (u32, u32),
+    pub supported_formats: &'static [&'static str],
+    /// The JSON schema of `ParseConfig`, so callers can see which fields (e.g.
+    /// `projections`, `schema`) are honored before authoring a config file.
+    pub config_schema: schemars::schema::RootSchema,
+}
+
+pub fn build_spec() -> Spec {
+    Spec {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol_version: PROTOCOL_VERSION,
+        supported_formats: SUPPORTED_FORMATS,
+        config_schema: schema_for!(ParseConfig),
+    }
+}
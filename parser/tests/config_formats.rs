@@ -0,0 +1,72 @@
+//! End-to-end coverage of config file format auto-detection (YAML/JSON/TOML) and overlay
+//! merging, via the `parse` subcommand.
+
+mod testutil;
+
+use parser::ParseConfig;
+use serde_json::json;
+use std::collections::BTreeMap;
+use testutil::*;
+
+#[test]
+fn yaml_base_with_json_overlay_merges_projections() {
+    let mut base = ParseConfig {
+        format: "csv".to_string(),
+        schema: json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "full_name": {"type": "string"}
+            },
+            "required": ["id", "full_name"]
+        }),
+        ..Default::default()
+    };
+    base.projections.insert("id".to_string(), "/id".to_string());
+
+    let mut overlay_projections = BTreeMap::new();
+    overlay_projections.insert("name".to_string(), "/full_name".to_string());
+
+    let result =
+        run_test_with_overlay(&base, &overlay_projections, input_bytes("id,name\n1,Alice\n"));
+
+    assert_eq!(result.exit_code, 0, "stderr/stdout: {}", result.raw_stdout);
+    assert_eq!(result.parsed, vec![json!({"id": 1, "full_name": "Alice"})]);
+}
+
+#[test]
+fn overlay_omitting_schema_leaves_the_base_schema_untouched() {
+    // The overlay here only ever writes a `projections` key (see `run_test_with_overlay`),
+    // so the base's real schema (which names every column) must survive the merge rather
+    // than being reset to the default permissive `true` schema. `zip` tells the two apart:
+    // the base schema types it as a string, while sampling its cell under a permissive
+    // schema would infer an integer.
+    let base = ParseConfig {
+        format: "csv".to_string(),
+        schema: json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "full_name": {"type": "string"},
+                "zip": {"type": "string"}
+            },
+            "required": ["id", "full_name"]
+        }),
+        ..Default::default()
+    };
+
+    let mut overlay_projections = BTreeMap::new();
+    overlay_projections.insert("name".to_string(), "/full_name".to_string());
+
+    let result = run_test_with_overlay(
+        &base,
+        &overlay_projections,
+        input_bytes("id,name,zip\n1,Alice,02139\n"),
+    );
+
+    assert_eq!(result.exit_code, 0, "stderr/stdout: {}", result.raw_stdout);
+    assert_eq!(
+        result.parsed,
+        vec![json!({"id": 1, "full_name": "Alice", "zip": "02139"})]
+    );
+}
@@ -0,0 +1,32 @@
+//! End-to-end coverage of `--emit-inferred-schema` for CSV input.
+
+mod testutil;
+
+use parser::ParseConfig;
+use serde_json::json;
+use testutil::*;
+
+#[test]
+fn emit_inferred_schema_prefixes_output_with_the_inferred_schema() {
+    let config = ParseConfig {
+        format: "csv".to_string(),
+        ..Default::default()
+    };
+
+    let result = run_test_emitting_schema(&config, input_bytes("id,name\n1,Alice\n2,Bob\n"));
+
+    assert_eq!(result.exit_code, 0, "stderr/stdout: {}", result.raw_stdout);
+    assert_eq!(result.parsed.len(), 3, "schema record + 2 documents");
+
+    let schema = &result.parsed[0]["inferred_schema"];
+    assert_eq!(schema["properties"]["id"]["type"], json!("integer"));
+    assert_eq!(schema["properties"]["name"]["type"], json!("string"));
+
+    assert_eq!(
+        result.parsed[1..].to_vec(),
+        vec![
+            json!({"id": 1, "name": "Alice"}),
+            json!({"id": 2, "name": "Bob"})
+        ]
+    );
+}
@@ -0,0 +1,33 @@
+//! End-to-end coverage of the `spec` subcommand.
+
+mod testutil;
+
+use testutil::*;
+
+#[test]
+fn spec_reports_protocol_version_and_supported_formats() {
+    let result = run_spec();
+
+    assert_eq!(result.exit_code, 0, "stderr/stdout: {}", result.raw_stdout);
+    assert_eq!(result.parsed.len(), 1);
+
+    let spec = &result.parsed[0];
+    assert_eq!(spec["protocol_version"], serde_json::json!([1, 0]));
+
+    let formats: Vec<&str> = spec["supported_formats"]
+        .as_array()
+        .expect("supported_formats should be an array")
+        .iter()
+        .map(|f| f.as_str().expect("format should be a string"))
+        .collect();
+    for expected in ["csv", "tsv", "json", "jsonl"] {
+        assert!(
+            formats.contains(&expected),
+            "expected {:?} in supported_formats, got {:?}",
+            expected,
+            formats
+        );
+    }
+
+    assert!(spec["config_schema"]["properties"]["projections"].is_object());
+}
@@ -2,6 +2,7 @@
 
 use parser::{Input, ParseConfig};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs::File;
 
 use tempdir::TempDir;
@@ -22,18 +23,82 @@ pub struct CommandResult {
     pub exit_code: i32,
 }
 
-pub fn run_test(config: &ParseConfig, mut input: Input) -> CommandResult {
-    use std::io::BufRead;
-    use std::process::{Command, Stdio};
+pub fn run_test(config: &ParseConfig, input: Input) -> CommandResult {
+    let tmp = TempDir::new("jsonl-parser-test").unwrap();
+    let cfg_path = tmp.path().join("config.json");
+    let mut cfg_file = File::create(&cfg_path).unwrap();
+    serde_json::to_writer_pretty(&mut cfg_file, config).expect("failed to write config");
+    std::mem::drop(cfg_file);
+
+    spawn_parse(&["--config-file", cfg_path.to_str().unwrap()], input)
+}
 
+/// Like `run_test`, but also passes `--emit-inferred-schema`, so the first line of
+/// `CommandResult::parsed` is the `{"inferred_schema": ...}` record rather than a parsed
+/// document.
+pub fn run_test_emitting_schema(config: &ParseConfig, input: Input) -> CommandResult {
     let tmp = TempDir::new("jsonl-parser-test").unwrap();
     let cfg_path = tmp.path().join("config.json");
     let mut cfg_file = File::create(&cfg_path).unwrap();
     serde_json::to_writer_pretty(&mut cfg_file, config).expect("failed to write config");
     std::mem::drop(cfg_file);
 
+    spawn_parse(
+        &[
+            "--config-file",
+            cfg_path.to_str().unwrap(),
+            "--emit-inferred-schema",
+        ],
+        input,
+    )
+}
+
+/// Like `run_test`, but writes `base` as YAML and `overlay_projections` as a JSON overlay
+/// file, exercising both format auto-detection and overlay merging (the overlay's
+/// `projections` should take precedence). The overlay only ever carries `projections` — it
+/// omits every other field entirely, mirroring a real overlay file that only wants to patch
+/// in a few extra projections without disturbing the base's schema.
+pub fn run_test_with_overlay(
+    base: &ParseConfig,
+    overlay_projections: &BTreeMap<String, String>,
+    input: Input,
+) -> CommandResult {
+    let tmp = TempDir::new("jsonl-parser-test").unwrap();
+
+    let base_path = tmp.path().join("config.yaml");
+    let mut base_file = File::create(&base_path).unwrap();
+    serde_yaml::to_writer(&mut base_file, base).expect("failed to write base config");
+    std::mem::drop(base_file);
+
+    let overlay_path = tmp.path().join("overlay.json");
+    let mut overlay_file = File::create(&overlay_path).unwrap();
+    serde_json::to_writer_pretty(
+        &mut overlay_file,
+        &serde_json::json!({ "projections": overlay_projections }),
+    )
+    .expect("failed to write overlay");
+    std::mem::drop(overlay_file);
+
+    spawn_parse(
+        &[
+            "--config-file",
+            base_path.to_str().unwrap(),
+            "--overlay-config-file",
+            overlay_path.to_str().unwrap(),
+        ],
+        input,
+    )
+}
+
+fn spawn_parse(args: &[&str], mut input: Input) -> CommandResult {
+    use std::io::BufRead;
+    use std::process::{Command, Stdio};
+
+    let mut full_args = vec!["parse"];
+    full_args.extend_from_slice(args);
+
     let mut process = Command::new("./target/debug/parser")
-        .args(&["parse", "--config-file", cfg_path.to_str().unwrap()])
+        .args(&full_args)
         .stdin(Stdio::piped())
         .stderr(Stdio::piped())
         .stdout(Stdio::piped())
@@ -67,6 +132,40 @@ pub fn run_test(config: &ParseConfig, mut input: Input) -> CommandResult {
     let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
     println!("parser stderr:\n{}", stderr);
 
+    CommandResult {
+        parsed,
+        exit_code,
+        raw_stdout,
+    }
+}
+
+/// Runs `parser spec` and deserializes each line of its stdout as a `Value`, mirroring
+/// `run_test`'s shape so callers can assert on the reported capabilities document the same
+/// way they assert on parsed records.
+pub fn run_spec() -> CommandResult {
+    use std::io::BufRead;
+    use std::process::Command;
+
+    let output = Command::new("./target/debug/parser")
+        .arg("spec")
+        .output()
+        .expect("failed to spawn parser process");
+
+    let exit_code = output.status.code().unwrap_or_else(|| {
+        println!("child process exited abnormally: {:?}", output.status);
+        -1
+    });
+    let mut parsed = Vec::new();
+    for line in output.stdout.lines() {
+        parsed.push(
+            serde_json::from_str(&line.unwrap()).expect("failed to deserialize parser output"),
+        );
+    }
+    let raw_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    println!("parser stderr:\n{}", stderr);
+
     CommandResult {
         parsed,
         exit_code,